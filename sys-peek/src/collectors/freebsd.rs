@@ -0,0 +1,41 @@
+use super::{
+    battery_readings, summarize_batteries, sysinfo_common, Collector, CollectorOptions, Snapshot,
+};
+use sysinfo::{System, SystemExt};
+
+/// FreeBSD/DragonFly data collection: `sysinfo` for CPU/memory/temps/
+/// processes, the `battery` crate for power.
+pub struct FreeBsdCollector {
+    sys: System,
+}
+
+impl FreeBsdCollector {
+    pub fn new() -> Self {
+        Self {
+            sys: System::new_all(),
+        }
+    }
+}
+
+impl Collector for FreeBsdCollector {
+    fn snapshot(&mut self, opts: &CollectorOptions) -> Snapshot {
+        let fields = sysinfo_common::collect(&mut self.sys, opts);
+        let batteries = battery_readings();
+        let (power_source, charge) = summarize_batteries(&batteries);
+
+        Snapshot {
+            cpu_usage: fields.cpu_usage,
+            per_core: fields.per_core,
+            load_average: fields.load_average,
+            memory_used: fields.memory_used,
+            memory_total: fields.memory_total,
+            memory_used_fmt: fields.memory_used_fmt,
+            memory_total_fmt: fields.memory_total_fmt,
+            power_source,
+            charge,
+            batteries,
+            temps: fields.temps,
+            processes: fields.processes,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use super::{
+    battery_readings, summarize_batteries, sysinfo_common, Collector, CollectorOptions, Snapshot,
+};
+use sysinfo::{System, SystemExt};
+
+/// Linux data collection: every field comes from `sysinfo` (sysfs/procfs
+/// under the hood) except power, which goes through the `battery` crate.
+pub struct LinuxCollector {
+    sys: System,
+}
+
+impl LinuxCollector {
+    pub fn new() -> Self {
+        Self {
+            sys: System::new_all(),
+        }
+    }
+}
+
+impl Collector for LinuxCollector {
+    fn snapshot(&mut self, opts: &CollectorOptions) -> Snapshot {
+        let fields = sysinfo_common::collect(&mut self.sys, opts);
+        let batteries = battery_readings();
+        let (power_source, charge) = summarize_batteries(&batteries);
+
+        Snapshot {
+            cpu_usage: fields.cpu_usage,
+            per_core: fields.per_core,
+            load_average: fields.load_average,
+            memory_used: fields.memory_used,
+            memory_total: fields.memory_total,
+            memory_used_fmt: fields.memory_used_fmt,
+            memory_total_fmt: fields.memory_total_fmt,
+            power_source,
+            charge,
+            batteries,
+            temps: fields.temps,
+            processes: fields.processes,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use super::{macos_power, summarize_batteries, sysinfo_common, BatteryReading, Collector, CollectorOptions, Snapshot};
+use sysinfo::{System, SystemExt};
+
+/// macOS data collection: `sysinfo` for CPU/memory/temps/processes, native
+/// IOKit `AppleSmartBattery` queries (see `macos_power`) for power instead
+/// of the generic `battery` crate, avoiding a `pmset` subprocess.
+pub struct MacosCollector {
+    sys: System,
+}
+
+impl MacosCollector {
+    pub fn new() -> Self {
+        Self {
+            sys: System::new_all(),
+        }
+    }
+}
+
+impl Collector for MacosCollector {
+    fn snapshot(&mut self, opts: &CollectorOptions) -> Snapshot {
+        let fields = sysinfo_common::collect(&mut self.sys, opts);
+
+        // Mac hardware only ever exposes a single `AppleSmartBattery`
+        // service, but we still model it as the same `Vec<BatteryReading>`
+        // every other OS uses so `summarize_batteries` stays the one place
+        // that derives the single-battery JSON/i3bar summary fields.
+        let batteries: Vec<BatteryReading> = match macos_power::query_power_info() {
+            Some(info) => vec![BatteryReading {
+                index: 0,
+                percentage: info.percentage,
+                state: macos_power::state_label(info.is_charging, info.on_ac, info.percentage)
+                    .to_string(),
+                source: macos_power::source_label(info.on_ac).to_string(),
+            }],
+            None => Vec::new(),
+        };
+        let (power_source, charge) = summarize_batteries(&batteries);
+
+        Snapshot {
+            cpu_usage: fields.cpu_usage,
+            per_core: fields.per_core,
+            load_average: fields.load_average,
+            memory_used: fields.memory_used,
+            memory_total: fields.memory_total,
+            memory_used_fmt: fields.memory_used_fmt,
+            memory_total_fmt: fields.memory_total_fmt,
+            power_source,
+            charge,
+            batteries,
+            temps: fields.temps,
+            processes: fields.processes,
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Native IOKit power-source queries for macOS.
+//!
+//! Replaces the old `pmset -g batt` shell-out (and the generic `battery`
+//! crate path) with a direct read of the `AppleSmartBattery` IOKit service,
+//! avoiding a subprocess on every poll and exposing the charging state the
+//! text parser used to drop.
+
+#![cfg(target_os = "macos")]
+
+use core_foundation::base::{CFGetTypeID, CFTypeRef, TCFType};
+use core_foundation::boolean::{CFBoolean, CFBooleanGetTypeID};
+use core_foundation::dictionary::CFDictionary;
+use core_foundation::number::{CFNumber, CFNumberGetTypeID};
+use core_foundation::string::CFString;
+use io_kit_sys::keys::kIOMasterPortDefault;
+use io_kit_sys::ret::kIOReturnSuccess;
+use io_kit_sys::{
+    IOObjectRelease, IORegistryEntryCreateCFProperties, IOServiceGetMatchingService,
+    IOServiceMatching,
+};
+use std::ffi::CString;
+
+/// Charge and source data read straight from the `AppleSmartBattery` service.
+pub struct PowerInfo {
+    pub percentage: u32,
+    pub is_charging: bool,
+    pub on_ac: bool,
+}
+
+/// Looks up `AppleSmartBattery` via `IOServiceGetMatchingService`, pulls its
+/// properties into a `CFDictionary` with `IORegistryEntryCreateCFProperties`,
+/// and extracts `CurrentCapacity`/`MaxCapacity`/`IsCharging`/`ExternalConnected`.
+/// Returns `None` on any machine without that service (e.g. a Hackintosh
+/// missing the driver, or a future sandboxed environment).
+pub fn query_power_info() -> Option<PowerInfo> {
+    unsafe {
+        let service_name = CString::new("AppleSmartBattery").ok()?;
+        let matching = IOServiceMatching(service_name.as_ptr());
+        if matching.is_null() {
+            return None;
+        }
+
+        let service = IOServiceGetMatchingService(kIOMasterPortDefault, matching);
+        if service == 0 {
+            return None;
+        }
+
+        let mut props: CFTypeRef = std::ptr::null();
+        let result =
+            IORegistryEntryCreateCFProperties(service, &mut props, std::ptr::null(), 0);
+        IOObjectRelease(service);
+
+        if result != kIOReturnSuccess || props.is_null() {
+            return None;
+        }
+
+        let dict = CFDictionary::<CFString, CFTypeRef>::wrap_under_create_rule(props as _);
+        let current = dict_i64(&dict, "CurrentCapacity")?;
+        let max = dict_i64(&dict, "MaxCapacity")?;
+        let is_charging = dict_bool(&dict, "IsCharging");
+        let on_ac = dict_bool(&dict, "ExternalConnected");
+
+        Some(PowerInfo {
+            percentage: percentage_from_capacity(current, max),
+            is_charging,
+            on_ac,
+        })
+    }
+}
+
+unsafe fn dict_i64(dict: &CFDictionary<CFString, CFTypeRef>, key: &str) -> Option<i64> {
+    let key = CFString::new(key);
+    let value = dict.find(key.as_CFTypeRef() as _)?;
+    if CFGetTypeID(*value) != CFNumberGetTypeID() {
+        return None;
+    }
+    CFNumber::wrap_under_get_rule(*value as _).to_i64()
+}
+
+unsafe fn dict_bool(dict: &CFDictionary<CFString, CFTypeRef>, key: &str) -> bool {
+    let key = CFString::new(key);
+    match dict.find(key.as_CFTypeRef() as _) {
+        Some(value) if CFGetTypeID(*value) == CFBooleanGetTypeID() => {
+            CFBoolean::wrap_under_get_rule(*value as _).into()
+        }
+        _ => false,
+    }
+}
+
+/// `CurrentCapacity * 100 / MaxCapacity`, guarding the desktop/unplugged
+/// case where `MaxCapacity` is reported as zero.
+fn percentage_from_capacity(current: i64, max: i64) -> u32 {
+    if max <= 0 {
+        return 0;
+    }
+    ((current * 100) / max).clamp(0, 100) as u32
+}
+
+/// `ExternalConnected` maps straight onto our AC/Battery vocabulary.
+pub fn source_label(on_ac: bool) -> &'static str {
+    if on_ac {
+        "AC"
+    } else {
+        "Battery"
+    }
+}
+
+/// `IsCharging` alone can't tell "topped off and still plugged in" from
+/// "unplugged and draining" — IOKit only flips it false once the battery
+/// stops actively accepting charge, which happens at 100% same as it does
+/// at 0%. Treat on-AC-at-100% as `Full`, matching how the `battery` crate's
+/// `State::Full` reads on every other OS.
+pub fn state_label(is_charging: bool, on_ac: bool, percentage: u32) -> &'static str {
+    if on_ac && percentage >= 100 {
+        "Full"
+    } else if is_charging {
+        "Charging"
+    } else {
+        "Discharging"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentage_from_capacity() {
+        assert_eq!(percentage_from_capacity(50, 100), 50);
+        assert_eq!(percentage_from_capacity(100, 100), 100);
+        assert_eq!(percentage_from_capacity(0, 100), 0);
+    }
+
+    #[test]
+    fn test_percentage_from_capacity_zero_max() {
+        // Some machines briefly report MaxCapacity as 0 right after a
+        // battery swap; don't divide by zero.
+        assert_eq!(percentage_from_capacity(10, 0), 0);
+    }
+
+    #[test]
+    fn test_source_label() {
+        assert_eq!(source_label(true), "AC");
+        assert_eq!(source_label(false), "Battery");
+    }
+
+    #[test]
+    fn test_state_label() {
+        assert_eq!(state_label(true, true, 80), "Charging");
+        assert_eq!(state_label(false, false, 50), "Discharging");
+        // Topped off and still plugged in: IsCharging is false, but this
+        // isn't "discharging" the way unplugged-at-50% is.
+        assert_eq!(state_label(false, true, 100), "Full");
+        assert_eq!(state_label(true, true, 100), "Full");
+    }
+}
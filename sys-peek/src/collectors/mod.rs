@@ -0,0 +1,250 @@
+//! Data collection, split one submodule per OS so the `#[cfg(target_os = ...)]`
+//! blocks that used to live inline in `main.rs` (see the old `report_power`)
+//! don't spread to every new data source. Each submodule implements
+//! [`Collector`] and fills in a single [`Snapshot`]; `main` just loops,
+//! asks the platform's collector for a snapshot, and hands it to a
+//! formatter in `output`.
+
+use serde::Serialize;
+
+mod sysinfo_common;
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "macos")]
+mod macos_power;
+#[cfg(target_os = "windows")]
+mod windows;
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+mod freebsd;
+
+#[cfg(target_os = "linux")]
+pub use linux::LinuxCollector as PlatformCollector;
+#[cfg(target_os = "macos")]
+pub use macos::MacosCollector as PlatformCollector;
+#[cfg(target_os = "windows")]
+pub use windows::WindowsCollector as PlatformCollector;
+#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))]
+pub use freebsd::FreeBsdCollector as PlatformCollector;
+
+/// Which metric to rank the process table by.
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessSort {
+    Cpu,
+    Mem,
+}
+
+/// Flags that shape what a [`Collector`] gathers each snapshot. These map
+/// straight onto the CLI flags in `main::Args`.
+pub struct CollectorOptions {
+    pub per_core: bool,
+    pub temps: bool,
+    pub fahrenheit: bool,
+    pub top: Option<usize>,
+    pub sort: ProcessSort,
+}
+
+/// One logical core's usage and clock speed.
+#[derive(Serialize)]
+pub struct CoreReading {
+    pub index: usize,
+    pub usage: f32,
+    pub frequency_mhz: u64,
+}
+
+/// The 1/5/15-minute load averages (zeros on platforms without the concept,
+/// e.g. Windows, rather than being absent).
+#[derive(Serialize)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
+}
+
+/// A hardware sensor reading, in the unit the user asked for via
+/// `--fahrenheit`.
+#[derive(Serialize)]
+pub struct TempReading {
+    pub label: String,
+    pub temperature: f32,
+    pub max: f32,
+    pub unit: &'static str,
+}
+
+/// One row of the top-N process table.
+#[derive(Serialize)]
+pub struct ProcessReading {
+    pub pid: String,
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+}
+
+/// One battery's charge, state, and AC-vs-battery source. Machines can have
+/// more than one (many ThinkPads and workstations do), so this is kept
+/// alongside the single-battery `power_source`/`charge` summary rather than
+/// replacing it.
+#[derive(Serialize)]
+pub struct BatteryReading {
+    pub index: usize,
+    pub percentage: u32,
+    pub state: String,
+    pub source: String,
+}
+
+/// Everything gathered in one poll. `per_core`, `load_average`, `temps` and
+/// `processes` are only populated when the matching CLI flag is set, so the
+/// default snapshot stays small; `charge` is `None` when there is no battery.
+///
+/// `power_source`/`charge` summarize the first (or only) battery for
+/// existing JSON/i3bar consumers; `batteries` carries the full per-battery
+/// breakdown for machines with more than one.
+#[derive(Serialize)]
+pub struct Snapshot {
+    pub cpu_usage: f32,
+    pub per_core: Option<Vec<CoreReading>>,
+    pub load_average: Option<LoadAverage>,
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub memory_used_fmt: String,
+    pub memory_total_fmt: String,
+    pub power_source: String,
+    pub charge: Option<u32>,
+    pub batteries: Vec<BatteryReading>,
+    pub temps: Option<Vec<TempReading>>,
+    pub processes: Option<Vec<ProcessReading>>,
+}
+
+/// Implemented once per OS. Each collector owns whatever handle it needs
+/// (a `sysinfo::System`, an IOKit service, ...) and turns the current
+/// machine state into a [`Snapshot`].
+pub trait Collector {
+    fn snapshot(&mut self, opts: &CollectorOptions) -> Snapshot;
+}
+
+/// Converts a Celsius reading to Fahrenheit.
+pub fn celsius_to_fahrenheit(celsius: f32) -> f32 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+/// Converts bytes into human-readable units (B, KB, MB, GB), used to fill
+/// `Snapshot::memory_used_fmt`/`memory_total_fmt` for JSON/i3bar consumers
+/// that don't want to reimplement the formatting themselves.
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    let units = ["B", "KB", "MB", "GB", "TB"];
+    let mut count = 0;
+    let mut f_bytes = bytes as f64;
+
+    while f_bytes >= 1024.0 && count < units.len() - 1 {
+        f_bytes /= 1024.0;
+        count += 1;
+    }
+    format!("{:.2} {}", f_bytes, units[count])
+}
+
+/// Enumerates every battery the OS exposes, shared by every OS except
+/// macOS, which reads `AppleSmartBattery` directly through IOKit instead
+/// (see `macos.rs`). Backed by the `battery` crate, which covers Linux,
+/// Windows, FreeBSD, and DragonFly. Desktops with no battery come back
+/// as an empty `Vec` rather than a misleading "Unknown".
+#[cfg(not(target_os = "macos"))]
+pub(crate) fn battery_readings() -> Vec<BatteryReading> {
+    let Ok(manager) = battery::Manager::new() else {
+        return Vec::new();
+    };
+    let Ok(batteries) = manager.batteries() else {
+        return Vec::new();
+    };
+
+    batteries
+        .filter_map(Result::ok)
+        .enumerate()
+        .map(|(index, battery)| {
+            battery_reading_from(index, battery.state_of_charge().value, battery.state())
+        })
+        .collect()
+}
+
+/// Turns one `battery` crate reading into our `BatteryReading` vocabulary,
+/// pulled out of `battery_readings` so the mapping is unit-testable without
+/// a real battery. Clamps to `0..=100` the same way `macos_power`'s
+/// `percentage_from_capacity` does, since a driver reporting
+/// `state_of_charge > 1.0` (seen in the wild on some buggy laptop EC
+/// firmware) would otherwise print a charge over 100%.
+#[cfg(not(target_os = "macos"))]
+fn battery_reading_from(index: usize, fraction: f32, state: battery::State) -> BatteryReading {
+    use battery::State;
+
+    let percentage = (fraction * 100.0).round().clamp(0.0, 100.0) as u32;
+    let source = match state {
+        State::Charging | State::Full => "AC",
+        _ => "Battery",
+    };
+
+    BatteryReading {
+        index,
+        percentage,
+        state: format!("{:?}", state),
+        source: source.to_string(),
+    }
+}
+
+/// Collapses the per-battery breakdown down to the single `power_source`/
+/// `charge` summary `Snapshot` carries for existing JSON/i3bar consumers:
+/// the first battery if there is one, "No battery" otherwise.
+pub(crate) fn summarize_batteries(batteries: &[BatteryReading]) -> (String, Option<u32>) {
+    match batteries.first() {
+        Some(battery) => (battery.source.clone(), Some(battery.percentage)),
+        None => ("No battery".to_string(), None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// UNIT TEST: Verifies the Celsius-to-Fahrenheit conversion at the
+    /// well-known reference points.
+    #[test]
+    fn test_celsius_to_fahrenheit() {
+        assert_eq!(celsius_to_fahrenheit(0.0), 32.0);
+        assert_eq!(celsius_to_fahrenheit(100.0), 212.0);
+        assert_eq!(celsius_to_fahrenheit(-40.0), -40.0);
+    }
+
+    /// UNIT TEST: Verifies exactly one OS's `pub use ... as PlatformCollector`
+    /// cfg arm compiled for this target, so `main` always has a
+    /// `PlatformCollector` to construct and the per-OS cfg gates above
+    /// (moved here from the old single-file `main.rs`) can't silently
+    /// resolve to zero or more than one collector.
+    #[test]
+    fn test_platform_compilation_gate() {
+        let _collector = PlatformCollector::new();
+    }
+
+    /// UNIT TEST: Verifies the `battery::State` -> AC/Battery mapping, the
+    /// counterpart to `macos_power`'s `test_source_label` on the other
+    /// power-reading path.
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_battery_reading_from_source() {
+        assert_eq!(battery_reading_from(0, 0.5, battery::State::Charging).source, "AC");
+        assert_eq!(battery_reading_from(0, 1.0, battery::State::Full).source, "AC");
+        assert_eq!(battery_reading_from(0, 0.5, battery::State::Discharging).source, "Battery");
+        assert_eq!(battery_reading_from(0, 0.0, battery::State::Empty).source, "Battery");
+    }
+
+    /// UNIT TEST: Verifies the percentage is clamped to `0..=100`, the
+    /// counterpart to `macos_power`'s `test_percentage_from_capacity`: some
+    /// battery drivers report `state_of_charge` fractions outside `0.0..=1.0`.
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_battery_reading_from_clamps_percentage() {
+        assert_eq!(battery_reading_from(0, 0.5, battery::State::Charging).percentage, 50);
+        assert_eq!(battery_reading_from(0, 1.5, battery::State::Full).percentage, 100);
+        assert_eq!(battery_reading_from(0, -0.2, battery::State::Unknown).percentage, 0);
+    }
+}
@@ -0,0 +1,121 @@
+//! The parts of a [`Snapshot`](super::Snapshot) that come from `sysinfo` and
+//! don't vary by OS: CPU, memory, components, and the process table. Power
+//! is the one thing that does vary, so every `Collector` gathers this, then
+//! fills in `power_source`/`charge` itself.
+
+use super::{CollectorOptions, CoreReading, LoadAverage, ProcessReading, ProcessSort, TempReading};
+use sysinfo::{ComponentExt, CpuExt, Process, ProcessExt, System, SystemExt};
+use std::thread;
+use std::time::Duration;
+
+/// Everything but power. Bundled so `System::new_all()` only has to happen
+/// once per process, in each OS collector's constructor.
+pub(crate) struct SysinfoFields {
+    pub cpu_usage: f32,
+    pub per_core: Option<Vec<CoreReading>>,
+    pub load_average: Option<LoadAverage>,
+    pub memory_used: u64,
+    pub memory_total: u64,
+    pub memory_used_fmt: String,
+    pub memory_total_fmt: String,
+    pub temps: Option<Vec<TempReading>>,
+    pub processes: Option<Vec<ProcessReading>>,
+}
+
+/// Refreshes `sys` and gathers every sysinfo-backed field. CPU and process
+/// counters need two refreshes bracketing a short sleep to compute a delta,
+/// the same two-phase refresh the original single-file version used.
+pub(crate) fn collect(sys: &mut System, opts: &CollectorOptions) -> SysinfoFields {
+    sys.refresh_cpu();
+    sys.refresh_processes();
+    thread::sleep(Duration::from_millis(200));
+    sys.refresh_cpu();
+    sys.refresh_processes();
+    sys.refresh_memory();
+    if opts.temps {
+        sys.refresh_components();
+    }
+
+    let cpu_usage = sys.global_cpu_info().cpu_usage();
+
+    let per_core = opts.per_core.then(|| {
+        sys.cpus()
+            .iter()
+            .enumerate()
+            .map(|(index, cpu)| CoreReading {
+                index,
+                usage: cpu.cpu_usage(),
+                frequency_mhz: cpu.frequency(),
+            })
+            .collect()
+    });
+
+    let load_average = opts.per_core.then(|| {
+        let load = sys.load_average();
+        LoadAverage {
+            one: load.one,
+            five: load.five,
+            fifteen: load.fifteen,
+        }
+    });
+
+    let temps = opts.temps.then(|| {
+        sys.components()
+            .iter()
+            .map(|component| {
+                let (temperature, max, unit) = if opts.fahrenheit {
+                    (
+                        super::celsius_to_fahrenheit(component.temperature()),
+                        super::celsius_to_fahrenheit(component.max()),
+                        "F",
+                    )
+                } else {
+                    (component.temperature(), component.max(), "C")
+                };
+                TempReading {
+                    label: component.label().to_string(),
+                    temperature,
+                    max,
+                    unit,
+                }
+            })
+            .collect()
+    });
+
+    let processes = opts.top.map(|top| top_processes(sys, opts.sort, top));
+
+    let memory_used = sys.used_memory();
+    let memory_total = sys.total_memory();
+
+    SysinfoFields {
+        cpu_usage,
+        per_core,
+        load_average,
+        memory_used,
+        memory_total,
+        memory_used_fmt: super::format_bytes(memory_used),
+        memory_total_fmt: super::format_bytes(memory_total),
+        temps,
+        processes,
+    }
+}
+
+/// Ranks processes by CPU% or RSS and keeps the top N, `top`-style.
+fn top_processes(sys: &System, sort: ProcessSort, top: usize) -> Vec<ProcessReading> {
+    let mut processes: Vec<&Process> = sys.processes().values().collect();
+    match sort {
+        ProcessSort::Cpu => processes.sort_by(|a, b| b.cpu_usage().total_cmp(&a.cpu_usage())),
+        ProcessSort::Mem => processes.sort_by_key(|p| std::cmp::Reverse(p.memory())),
+    }
+
+    processes
+        .into_iter()
+        .take(top)
+        .map(|process| ProcessReading {
+            pid: process.pid().to_string(),
+            name: process.name().to_string(),
+            cpu_usage: process.cpu_usage(),
+            memory_bytes: process.memory(),
+        })
+        .collect()
+}
@@ -0,0 +1,43 @@
+use super::{
+    battery_readings, summarize_batteries, sysinfo_common, Collector, CollectorOptions, Snapshot,
+};
+use sysinfo::{System, SystemExt};
+
+/// Windows data collection: `sysinfo` for CPU/memory/temps/processes, the
+/// `battery` crate (which wraps the Windows power APIs) for power. Load
+/// average has no meaning on Windows; `sysinfo` reports zeros rather than
+/// failing, which we pass through unchanged.
+pub struct WindowsCollector {
+    sys: System,
+}
+
+impl WindowsCollector {
+    pub fn new() -> Self {
+        Self {
+            sys: System::new_all(),
+        }
+    }
+}
+
+impl Collector for WindowsCollector {
+    fn snapshot(&mut self, opts: &CollectorOptions) -> Snapshot {
+        let fields = sysinfo_common::collect(&mut self.sys, opts);
+        let batteries = battery_readings();
+        let (power_source, charge) = summarize_batteries(&batteries);
+
+        Snapshot {
+            cpu_usage: fields.cpu_usage,
+            per_core: fields.per_core,
+            load_average: fields.load_average,
+            memory_used: fields.memory_used,
+            memory_total: fields.memory_total,
+            memory_used_fmt: fields.memory_used_fmt,
+            memory_total_fmt: fields.memory_total_fmt,
+            power_source,
+            charge,
+            batteries,
+            temps: fields.temps,
+            processes: fields.processes,
+        }
+    }
+}
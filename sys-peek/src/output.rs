@@ -0,0 +1,170 @@
+//! Formatters that turn a [`Snapshot`] into what the user asked for via
+//! `--output`: plain text, one JSON object per line, or the i3bar/Waybar
+//! JSON-array protocol.
+
+use crate::collectors::{format_bytes, Snapshot};
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputMode {
+    Text,
+    Json,
+    I3bar,
+}
+
+/// Prints the i3bar protocol header (`{"version":1}` followed by the
+/// opening `[` of the infinite array). Call once before the first snapshot.
+pub fn print_i3bar_header() {
+    println!("{{\"version\":1}}");
+    println!("[");
+}
+
+/// Prints the closing `]` of the i3bar array. Call once after the loop
+/// exits, so `--runs N` still produces valid JSON.
+pub fn print_i3bar_footer() {
+    println!("]");
+}
+
+/// `is_first` is only used for `OutputMode::I3bar`: the i3bar array wants a
+/// leading `,` before every entry after the first rather than a trailing
+/// one after every entry, so the final line isn't followed by a comma
+/// before `print_i3bar_footer`'s `]`.
+pub fn print_snapshot(snapshot: &Snapshot, mode: OutputMode, is_first: bool) {
+    match mode {
+        OutputMode::Text => print_text(snapshot),
+        OutputMode::Json => println!("{}", serde_json::to_string(snapshot).unwrap()),
+        OutputMode::I3bar => print_i3bar_blocks(snapshot, is_first),
+    }
+}
+
+fn print_text(snapshot: &Snapshot) {
+    println!("CPU Usage:      {:.2}%", snapshot.cpu_usage);
+
+    if let Some(cores) = &snapshot.per_core {
+        for core in cores {
+            println!(
+                "  Core {:<3}      {:.2}% @ {} MHz",
+                core.index, core.usage, core.frequency_mhz
+            );
+        }
+    }
+    if let Some(load) = &snapshot.load_average {
+        println!(
+            "  Load Average:  {:.2} (1m) {:.2} (5m) {:.2} (15m)",
+            load.one, load.five, load.fifteen
+        );
+    }
+
+    println!(
+        "Memory:         {} / {} used",
+        snapshot.memory_used_fmt, snapshot.memory_total_fmt
+    );
+
+    for battery in &snapshot.batteries {
+        println!(
+            "Battery {}:      {}% ({}), Source: {}",
+            battery.index, battery.percentage, battery.state, battery.source
+        );
+    }
+
+    println!("Power Source:   {}", snapshot.power_source);
+    if let Some(charge) = snapshot.charge {
+        println!("Charge:         {}%", charge);
+    }
+
+    if let Some(processes) = &snapshot.processes {
+        println!("Top {} processes:", processes.len());
+        for process in processes {
+            println!(
+                "  {:<8} {:<20} {:>6.2}%  {}",
+                process.pid,
+                process.name,
+                process.cpu_usage,
+                format_bytes(process.memory_bytes)
+            );
+        }
+    }
+
+    if let Some(temps) = &snapshot.temps {
+        for temp in temps {
+            println!(
+                "  {:<20} {:.1}°{} (max {:.1}°{})",
+                temp.label, temp.temperature, temp.unit, temp.max, temp.unit
+            );
+        }
+    }
+}
+
+/// Prints one i3status-protocol array entry: `[block, block, ...]`, preceded
+/// by a `,` for every entry but the first so the array never ends in a
+/// trailing comma before `print_i3bar_footer`'s `]`. Each block is
+/// `{"full_text": ..., "color": ...}`, matching the format i3status/Waybar
+/// expect on every line after the initial header.
+fn print_i3bar_blocks(snapshot: &Snapshot, is_first: bool) {
+    let cpu_color = if snapshot.cpu_usage > 80.0 {
+        "#FF0000"
+    } else {
+        "#00FF00"
+    };
+    let blocks = serde_json::json!([
+        {"full_text": format!("CPU: {:.1}%", snapshot.cpu_usage), "color": cpu_color},
+        {"full_text": format!(
+            "Mem: {} / {}",
+            snapshot.memory_used_fmt, snapshot.memory_total_fmt
+        ), "color": "#FFFFFF"},
+        {"full_text": match snapshot.charge {
+            Some(c) => format!("{}: {}%", snapshot.power_source, c),
+            None => snapshot.power_source.clone(),
+        }, "color": "#FFFFFF"},
+    ]);
+    if is_first {
+        println!("{}", blocks);
+    } else {
+        println!(",{}", blocks);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// UNIT TEST: Verifies the human-readable byte conversion.
+    /// Senior engineers test edge cases like 0 bytes and exact boundaries.
+    #[test]
+    fn test_format_bytes_logic() {
+        assert_eq!(format_bytes(0), "0.00 B");
+        assert_eq!(format_bytes(1023), "1023.00 B");
+        assert_eq!(format_bytes(1024), "1.00 KB");
+        assert_eq!(format_bytes(1024 * 1024), "1.00 MB");
+        assert_eq!(format_bytes(1024 * 1024 * 1024), "1.00 GB");
+        // Test a non-exact value
+        assert_eq!(format_bytes(1500), "1.46 KB");
+    }
+
+    /// UNIT TEST: Verifies the JSON snapshot serializes with the field
+    /// names external consumers (status bars, dashboards) depend on.
+    #[test]
+    fn test_snapshot_json_serialization() {
+        let snapshot = Snapshot {
+            cpu_usage: 12.5,
+            per_core: None,
+            load_average: None,
+            memory_used: 1024,
+            memory_total: 2048,
+            memory_used_fmt: "1.00 KB".to_string(),
+            memory_total_fmt: "2.00 KB".to_string(),
+            power_source: "AC".to_string(),
+            charge: Some(87),
+            batteries: Vec::new(),
+            temps: None,
+            processes: None,
+        };
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(json.contains("\"cpu_usage\":12.5"));
+        assert!(json.contains("\"memory_used\":1024"));
+        assert!(json.contains("\"memory_used_fmt\":\"1.00 KB\""));
+        assert!(json.contains("\"memory_total_fmt\":\"2.00 KB\""));
+        assert!(json.contains("\"power_source\":\"AC\""));
+        assert!(json.contains("\"charge\":87"));
+    }
+}